@@ -0,0 +1,141 @@
+use crate::{Next, Request, Response};
+
+/// Runs `next` only for requests with Basic credentials matching one of `credentials`, meant to
+/// be called from middleware
+///
+/// Other requests get a 401 with a `WWW-Authenticate` header naming `realm`, which makes
+/// browsers ask for a username and password. Handlers behind it can read the username with
+/// `Request::basic_auth`
+///
+/// # Example
+///
+/// ```
+/// use http_library::{basic_auth, Next, Request, Response, Router};
+///
+/// let mut r = Router::new("127.0.0.1:12345");
+/// r.use_middleware(admin_only);
+/// r.handle_func("/", whoami, vec!["GET"]);
+///
+/// fn admin_only(req: &Request, next: Next) -> Response {
+///     basic_auth(req, next, "admin", &[("admin", "hunter2")])
+/// }
+///
+/// fn whoami(req: &Request) -> Response {
+///     let (user, _) = req.basic_auth().unwrap_or_default();
+///     Response::new(200, format!("hello {}", user))
+/// }
+/// ```
+pub fn basic_auth(
+    req: &Request,
+    next: Next,
+    realm: &str,
+    credentials: &[(&str, &str)],
+) -> Response {
+    let authorized = req.basic_auth().is_some_and(|(user, password)| {
+        credentials.iter().any(|(u, p)| {
+            // check both so the time taken doesn't reveal which was wrong
+            let user_ok = constant_time_eq(user.as_bytes(), u.as_bytes());
+            let password_ok = constant_time_eq(password.as_bytes(), p.as_bytes());
+            user_ok & password_ok
+        })
+    });
+    if authorized {
+        return next.run(req);
+    }
+
+    Response::new(401, "unauthorized").with_header(
+        "WWW-Authenticate",
+        &format!(
+            "Basic realm=\"{}\", charset=\"UTF-8\"",
+            realm.replace('"', "")
+        ),
+    )
+}
+
+/// Splits an `Authorization: Basic ...` value into username and password
+pub fn parse_basic(header: &str) -> Option<(String, String)> {
+    let (scheme, encoded) = header.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("Basic") {
+        return None;
+    }
+
+    let decoded = String::from_utf8(base64_decode(encoded.trim())?).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_owned(), password.to_owned()))
+}
+
+/// Compares without returning early, so timing doesn't reveal how much of a secret matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Decodes standard base64, padding optional
+fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=').as_bytes();
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut bits = 0u32;
+    let mut count = 0;
+
+    for &c in input {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = (bits << 6) | u32::from(value);
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            out.push((bits >> count) as u8);
+            bits &= (1 << count) - 1;
+        }
+    }
+
+    // a single leftover character can't encode a whole byte
+    if count >= 6 {
+        return None;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_base64() {
+        assert_eq!(
+            base64_decode("YWRtaW46aHVudGVyMg==").unwrap(),
+            b"admin:hunter2"
+        );
+        assert_eq!(
+            base64_decode("YWRtaW46aHVudGVyMg").unwrap(),
+            b"admin:hunter2"
+        );
+        assert_eq!(base64_decode("").unwrap(), b"");
+        assert_eq!(base64_decode("YQ").unwrap(), b"a");
+        assert!(base64_decode("Y").is_none());
+        assert!(base64_decode("YW*=").is_none());
+    }
+
+    #[test]
+    fn parses_basic_credentials() {
+        assert_eq!(
+            parse_basic("Basic YWRtaW46aHVudGVyMg=="),
+            Some(("admin".to_owned(), "hunter2".to_owned()))
+        );
+        // only the first colon separates, passwords can contain more
+        assert_eq!(
+            parse_basic("basic dTpwOnE="),
+            Some(("u".to_owned(), "p:q".to_owned()))
+        );
+        assert_eq!(parse_basic("Bearer YWRtaW46aHVudGVyMg=="), None);
+        assert_eq!(parse_basic("Basic bm9jb2xvbg=="), None);
+    }
+}
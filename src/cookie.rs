@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::url;
+
+/// A cookie to send to the client with `Response::set_cookie`
+///
+/// # Example
+///
+/// ```
+/// use http_library::{Cookie, SameSite};
+///
+/// let cookie = Cookie::new("sid", "abc")
+///     .path("/")
+///     .max_age(3600)
+///     .secure(true)
+///     .same_site(SameSite::Strict);
+///
+/// assert_eq!(cookie.to_string(), "sid=abc; Path=/; Max-Age=3600; Secure; SameSite=Strict");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    path: Option<String>,
+    domain: Option<String>,
+    max_age: Option<i64>,
+    http_only: bool,
+    secure: bool,
+    same_site: Option<SameSite>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl Cookie {
+    pub fn new(name: &str, value: &str) -> Cookie {
+        Cookie {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            path: None,
+            domain: None,
+            max_age: None,
+            http_only: false,
+            secure: false,
+            same_site: None,
+        }
+    }
+
+    pub fn path(mut self, path: &str) -> Cookie {
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    pub fn domain(mut self, domain: &str) -> Cookie {
+        self.domain = Some(domain.to_owned());
+        self
+    }
+
+    /// Seconds until the cookie expires, zero or less deletes it
+    pub fn max_age(mut self, seconds: i64) -> Cookie {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    pub fn http_only(mut self, http_only: bool) -> Cookie {
+        self.http_only = http_only;
+        self
+    }
+
+    pub fn secure(mut self, secure: bool) -> Cookie {
+        self.secure = secure;
+        self
+    }
+
+    pub fn same_site(mut self, same_site: SameSite) -> Cookie {
+        self.same_site = Some(same_site);
+        self
+    }
+}
+
+/// Formats the cookie as the value of a `Set-Cookie` header
+impl Display for Cookie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, encode(&self.value))?;
+
+        if let Some(ref path) = self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(ref domain) = self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age)?;
+        }
+        if self.http_only {
+            write!(f, "; HttpOnly")?;
+        }
+        if self.secure {
+            write!(f, "; Secure")?;
+        }
+        match self.same_site {
+            Some(SameSite::Strict) => write!(f, "; SameSite=Strict")?,
+            Some(SameSite::Lax) => write!(f, "; SameSite=Lax")?,
+            Some(SameSite::None) => write!(f, "; SameSite=None")?,
+            None => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Percent-encodes the bytes that aren't allowed in a cookie value
+fn encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'%' | b' ' | b'"' | b',' | b';' | b'\\' | 0..=0x1f | 0x7f.. => {
+                out.push_str(&format!("%{:02X}", b))
+            }
+            b => out.push(b as char),
+        }
+    }
+    out
+}
+
+/// Parses the value of a `Cookie` header like `sid=abc; theme=dark` into name/value pairs
+///
+/// Values are percent-decoded and may be wrapped in double quotes. Pairs without a name or `=`
+/// are skipped
+pub fn parse(header: &str) -> HashMap<String, String> {
+    header
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            let value = url::percent_decode(value).unwrap_or_else(|| value.to_owned());
+
+            Some((name.to_owned(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cookie_header() {
+        let cookies = parse("sid=abc; theme=dark");
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies["sid"], "abc");
+        assert_eq!(cookies["theme"], "dark");
+    }
+
+    #[test]
+    fn formats_set_cookie() {
+        let cookie = Cookie::new("sid", "a b;c")
+            .path("/")
+            .domain("example.com")
+            .max_age(0)
+            .http_only(true)
+            .secure(true)
+            .same_site(SameSite::None);
+
+        assert_eq!(
+            cookie.to_string(),
+            "sid=a%20b%3Bc; Path=/; Domain=example.com; Max-Age=0; HttpOnly; Secure; SameSite=None"
+        );
+        assert_eq!(parse(&cookie.to_string())["sid"], "a b;c");
+    }
+
+    #[test]
+    fn handles_odd_values() {
+        let cookies = parse(r#"name=hello%20world;quoted="x y";empty=; broken; =nameless;"#);
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies["name"], "hello world");
+        assert_eq!(cookies["quoted"], "x y");
+        assert_eq!(cookies["empty"], "");
+
+        assert!(parse("").is_empty());
+    }
+}
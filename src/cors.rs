@@ -0,0 +1,130 @@
+use crate::{Method, Request, Response};
+
+/// Which cross-origin requests browsers may make, set with `Router::set_cors`
+///
+/// Nothing is allowed until origins are added, `"*"` allowing every origin. Simple requests get
+/// `Access-Control-Allow-Origin` and preflight `OPTIONS` requests are answered with a 204 without
+/// reaching any route
+///
+/// # Example
+///
+/// ```
+/// use http_library::{Cors, Router};
+///
+/// let mut r = Router::new("127.0.0.1:12345");
+/// r.set_cors(
+///     Cors::new()
+///         .allow_origin("https://app.example.com")
+///         .allow_methods(vec!["GET", "POST", "DELETE"])
+///         .allow_headers(vec!["Content-Type", "Authorization"])
+///         .max_age(600),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cors {
+    origins: Vec<String>,
+    methods: Vec<Method>,
+    headers: Vec<String>,
+    max_age: Option<u64>,
+}
+
+impl Cors {
+    /// Allows no origins, with `GET`, `HEAD` and `POST` as the allowed methods
+    pub fn new() -> Cors {
+        Cors {
+            origins: vec![],
+            methods: vec![Method::Get, Method::Head, Method::Post],
+            headers: vec![],
+            max_age: None,
+        }
+    }
+
+    /// Allows requests from `origin`, like `https://example.com`, or from anywhere with `"*"`
+    pub fn allow_origin(mut self, origin: &str) -> Cors {
+        self.origins.push(origin.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// Replaces the methods preflight requests may ask for
+    pub fn allow_methods(mut self, methods: Vec<impl Into<Method>>) -> Cors {
+        self.methods = methods.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Request headers preflight requests may ask for, beyond those browsers always allow
+    pub fn allow_headers(mut self, headers: Vec<&str>) -> Cors {
+        self.headers = headers.into_iter().map(str::to_owned).collect();
+        self
+    }
+
+    /// Seconds browsers may cache a preflight response for
+    pub fn max_age(mut self, seconds: u64) -> Cors {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    fn any_origin(&self) -> bool {
+        self.origins.iter().any(|o| o == "*")
+    }
+
+    /// The `Access-Control-Allow-Origin` value for a request from `origin`, `None` if it isn't
+    /// allowed
+    fn allowed_origin<'a>(&self, origin: &'a str) -> Option<&'a str> {
+        if self.any_origin() {
+            return Some("*");
+        }
+
+        self.origins
+            .iter()
+            .any(|o| o.eq_ignore_ascii_case(origin))
+            .then_some(origin)
+    }
+
+    /// Whether `req` is a preflight request, which is answered by `preflight` instead of a route
+    pub(crate) fn is_preflight(&self, req: &Request) -> bool {
+        req.method == Method::Options
+            && req.header("Origin").is_some()
+            && req.header("Access-Control-Request-Method").is_some()
+    }
+
+    /// Answers a preflight request, leaving out the `Access-Control-Allow-*` headers if the
+    /// origin isn't allowed so the browser refuses the real request
+    pub(crate) fn preflight(&self, req: &Request) -> Response {
+        let mut res = Response::empty(204);
+        self.apply(req, &mut res);
+        if !res.headers.contains("Access-Control-Allow-Origin") {
+            return res;
+        }
+
+        let methods: Vec<&str> = self.methods.iter().map(Method::as_str).collect();
+        res.set_header("Access-Control-Allow-Methods", &methods.join(", "));
+        if !self.headers.is_empty() {
+            res.set_header("Access-Control-Allow-Headers", &self.headers.join(", "));
+        }
+        if let Some(max_age) = self.max_age {
+            res.set_header("Access-Control-Max-Age", &max_age.to_string());
+        }
+        res
+    }
+
+    /// Adds `Access-Control-Allow-Origin` to the response if the request's origin is allowed
+    pub(crate) fn apply(&self, req: &Request, res: &mut Response) {
+        // the response depends on the origin unless every origin gets the same answer
+        if !self.any_origin() {
+            res.add_vary("Origin");
+        }
+
+        let Some(origin) = req.header("Origin") else {
+            return;
+        };
+        if let Some(allowed) = self.allowed_origin(origin) {
+            res.set_header("Access-Control-Allow-Origin", allowed);
+        }
+    }
+}
+
+impl Default for Cors {
+    fn default() -> Cors {
+        Cors::new()
+    }
+}
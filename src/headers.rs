@@ -0,0 +1,81 @@
+/// Header fields in the order they were added
+///
+/// Names are matched ignoring case and a name can appear more than once, as with repeated
+/// `X-Forwarded-For` or `Set-Cookie` headers. Carriage returns and line feeds are dropped from
+/// names and values as they are added, so a value taken from user input can't end its header
+/// line and start another
+///
+/// # Example
+///
+/// ```
+/// use http_library::Headers;
+///
+/// let mut headers = Headers::new();
+/// headers.append("Accept", "text/html");
+/// headers.append("accept", "application/json");
+///
+/// assert_eq!(headers.get("ACCEPT"), Some("text/html"));
+/// assert_eq!(headers.get_all("Accept").collect::<Vec<_>>(), ["text/html", "application/json"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Headers(Vec<(String, String)>);
+
+impl Headers {
+    pub fn new() -> Headers {
+        Headers(vec![])
+    }
+
+    /// Returns the first value for `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns every value for `name` in the order they were added
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Adds a value for `name`, keeping any existing ones
+    pub fn append(&mut self, name: &str, value: &str) {
+        self.0.push((strip_forbidden(name), strip_forbidden(value)));
+    }
+
+    /// Sets `name` to `value`, replacing any existing values
+    pub fn insert(&mut self, name: &str, value: &str) {
+        self.remove(name);
+        self.append(name, value);
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|(k, _)| !k.eq_ignore_ascii_case(name));
+    }
+
+    /// Iterates over every name and value pair in order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Removes CR and LF, which would end the header line early, and NUL, which isn't allowed in a
+/// field at all
+fn strip_forbidden(s: &str) -> String {
+    s.replace(['\r', '\n', '\0'], "")
+}
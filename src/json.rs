@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Write};
+
+/// Json object with every key and value written as a string
+pub struct Json<K, V>(pub HashMap<K, V>);
+
+impl<K, V> Display for Json<K, V>
+where
+    K: Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut string = String::from("{");
+
+        for (i, (k, v)) in self.0.iter().enumerate() {
+            string.push_str(&format!(
+                "\"{}\": \"{}\"",
+                escape(&k.to_string()),
+                escape(&v.to_string())
+            ));
+            if i != (self.0.len() - 1) {
+                string.push(',');
+            }
+        }
+
+        string.push('}');
+        write!(f, "{}", string)
+    }
+}
+
+/// A json value that can be nested and keeps numbers and booleans unquoted
+///
+/// # Example
+///
+/// ```
+/// use http_library::JsonValue;
+/// use std::collections::HashMap;
+///
+/// let mut user = HashMap::new();
+/// user.insert("age".to_owned(), JsonValue::Number(30.0));
+/// user.insert(
+///     "tags".to_owned(),
+///     JsonValue::Array(vec![JsonValue::String("admin".to_owned()), JsonValue::Null]),
+/// );
+///
+/// let mut data = HashMap::new();
+/// data.insert("user".to_owned(), JsonValue::Object(user));
+/// let data = JsonValue::Object(data);
+///
+/// assert!(data.to_string().contains(r#""age": 30"#));
+/// assert!(data.to_string().contains(r#""tags": ["admin",null]"#));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(HashMap<String, JsonValue>),
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            // json has no representation for NaN or infinity
+            JsonValue::Number(n) if !n.is_finite() => write!(f, "null"),
+            JsonValue::Number(n) => write!(f, "{}", n),
+            JsonValue::String(s) => write!(f, "\"{}\"", escape(s)),
+            JsonValue::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(map) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in map.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "\"{}\": {}", escape(k), v)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl JsonValue {
+    /// Parses a json document
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_library::JsonValue;
+    ///
+    /// let value = JsonValue::parse(r#"{"ok": true}"#).unwrap();
+    /// assert!(JsonValue::parse("{").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<JsonValue, JsonError> {
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            pos: 0,
+        };
+
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos != parser.input.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+
+        Ok(value)
+    }
+}
+
+/// Error from parsing invalid json
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonError {
+    /// Byte offset in the input where the error was found
+    pub offset: usize,
+    msg: &'static str,
+}
+
+impl JsonError {
+    pub(crate) fn new(offset: usize, msg: &'static str) -> JsonError {
+        JsonError { offset, msg }
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid json: {} at byte {}", self.msg, self.offset)
+    }
+}
+
+impl Error for JsonError {}
+
+/// Deepest nesting of arrays and objects accepted, so hostile input can't overflow the stack
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &'static str) -> JsonError {
+        JsonError::new(self.pos, msg)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if self.input[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("unexpected character"))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.expect("null", JsonValue::Null),
+            Some(b't') => self.expect("true", JsonValue::Bool(true)),
+            Some(b'f') => self.expect("false", JsonValue::Bool(false)),
+            Some(b'"') => Ok(JsonValue::String(self.string()?)),
+            Some(b'[') => self.array(depth),
+            Some(b'{') => self.object(depth),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut items = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut map = HashMap::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(map));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected string key"));
+            }
+            let key = self.string()?;
+
+            self.skip_whitespace();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.pos += 1;
+
+            map.insert(key, self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(map));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let from = p.pos;
+            while let Some(b'0'..=b'9') = p.peek() {
+                p.pos += 1;
+            }
+            p.pos > from
+        };
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'0') {
+            self.pos += 1;
+        } else if !digits(self) {
+            return Err(self.error("invalid number"));
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !digits(self) {
+                return Err(self.error("invalid number"));
+            }
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("invalid number"));
+            }
+        }
+
+        // only ascii was consumed above so this can't fail
+        let text = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        text.parse()
+            .map(JsonValue::Number)
+            .map_err(|_| JsonError::new(start, "invalid number"))
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.pos += 1;
+        let mut out = Vec::new();
+
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    // input came from a &str and escapes only add valid chars
+                    return Ok(String::from_utf8(out).unwrap());
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.peek() {
+                        Some(b'u') => {
+                            self.pos += 1;
+                            self.unicode_escape()?
+                        }
+                        Some(b) => {
+                            let c = match b {
+                                b'"' => '"',
+                                b'\\' => '\\',
+                                b'/' => '/',
+                                b'b' => '\u{08}',
+                                b'f' => '\u{0c}',
+                                b'n' => '\n',
+                                b'r' => '\r',
+                                b't' => '\t',
+                                _ => return Err(self.error("invalid escape")),
+                            };
+                            self.pos += 1;
+                            c
+                        }
+                        None => return Err(self.error("unterminated string")),
+                    };
+                    out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                Some(b) if b < 0x20 => return Err(self.error("control character in string")),
+                Some(b) => {
+                    out.push(b);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Reads the hex digits after `\u`, combining surrogate pairs
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.input[self.pos..].starts_with(b"\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let low = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let hex = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u32::from_str_radix(h, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(hex)
+    }
+}
+
+/// Escapes `s` for use inside a json string literal
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                write!(out, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_nested_values() {
+        let mut inner = HashMap::new();
+        inner.insert("n".to_owned(), JsonValue::Number(1.5));
+        let value = JsonValue::Array(vec![
+            JsonValue::Number(42.0),
+            JsonValue::Number(-3.0),
+            JsonValue::Bool(true),
+            JsonValue::Null,
+            JsonValue::String("a\"b".to_owned()),
+            JsonValue::Object(inner),
+            JsonValue::Array(vec![]),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r#"[42,-3,true,null,"a\"b",{"n": 1.5},[]]"#
+        );
+        assert_eq!(JsonValue::Number(f64::NAN).to_string(), "null");
+    }
+
+    #[test]
+    fn parses_documents() {
+        let value = JsonValue::parse(
+            r#" {"name": "a\"b\u00e9\ud83d\ude00", "n": -1.5e2, "list": [1, true, null], "o": {}} "#,
+        )
+        .unwrap();
+
+        let mut expected = HashMap::new();
+        expected.insert(
+            "name".to_owned(),
+            JsonValue::String("a\"b\u{e9}\u{1f600}".to_owned()),
+        );
+        expected.insert("n".to_owned(), JsonValue::Number(-150.0));
+        expected.insert(
+            "list".to_owned(),
+            JsonValue::Array(vec![
+                JsonValue::Number(1.0),
+                JsonValue::Bool(true),
+                JsonValue::Null,
+            ]),
+        );
+        expected.insert("o".to_owned(), JsonValue::Object(HashMap::new()));
+        assert_eq!(value, JsonValue::Object(expected));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        for input in [
+            "",
+            "{",
+            "[1,]",
+            "{\"a\" 1}",
+            "01",
+            "1.",
+            "\"\\x\"",
+            "\"\\ud800\"",
+            "nul",
+            "[] []",
+        ] {
+            assert!(
+                JsonValue::parse(input).is_err(),
+                "{:?} should not parse",
+                input
+            );
+        }
+
+        assert_eq!(JsonValue::parse("[1, }").unwrap_err().offset, 4);
+        assert!(JsonValue::parse(&"[".repeat(1000)).is_err());
+    }
+
+    #[test]
+    fn round_trips_escaped_output() {
+        let mut data = HashMap::new();
+        data.insert("k", "line\n\"quoted\"\t\u{01}");
+
+        let parsed = JsonValue::parse(&Json(data).to_string()).unwrap();
+        let mut expected = HashMap::new();
+        expected.insert(
+            "k".to_owned(),
+            JsonValue::String("line\n\"quoted\"\t\u{01}".to_owned()),
+        );
+        assert_eq!(parsed, JsonValue::Object(expected));
+    }
+
+    #[test]
+    fn escapes_strings() {
+        let mut data = HashMap::new();
+        data.insert("quote\"key", "say \"hi\"\nback\\slash\t\u{01}");
+
+        assert_eq!(
+            Json(data).to_string(),
+            r#"{"quote\"key": "say \"hi\"\nback\\slash\t\u0001"}"#
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::Method;
+
+/// What was asked for and how it was answered, passed to the logger set with
+/// `Router::set_access_log`
+///
+/// # Example
+///
+/// ```
+/// use http_library::{AccessLog, Method};
+/// use std::time::Duration;
+///
+/// let entry = AccessLog {
+///     method: Method::Get,
+///     path: "/users/1".to_owned(),
+///     route: Some("/users/:id".to_owned()),
+///     status: 200,
+///     size: Some(42),
+///     duration: Duration::from_micros(1500),
+/// };
+///
+/// assert_eq!(entry.to_string(), "GET /users/1 200 42 1.500ms");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessLog {
+    pub method: Method,
+    /// Request path, percent-decoded
+    pub path: String,
+    /// Pattern of the route that matched, see `Request::matched_route`. Grouping by it keeps
+    /// metrics from having a series per user id
+    pub route: Option<String>,
+    pub status: u16,
+    /// Body size in bytes as sent, `None` for streamed bodies whose size isn't known up front
+    pub size: Option<u64>,
+    /// Time spent routing and running the handler and middleware
+    pub duration: Duration,
+}
+
+impl Display for AccessLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} ", self.method, self.path, self.status)?;
+        match self.size {
+            Some(size) => write!(f, "{}", size)?,
+            None => write!(f, "-")?,
+        }
+        write!(f, " {:.3}ms", self.duration.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Logger that prints each entry to stdout on its own line
+pub fn print_access_log(entry: &AccessLog) {
+    println!("{}", entry);
+}
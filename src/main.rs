@@ -1,16 +1,21 @@
-use std::{collections::HashMap, env, fs};
+use std::{collections::HashMap, env, error::Error, fs};
 
-use http_library::{Request, Response, Router};
+use http_library::{print_access_log, resolve_path, Method, Request, Response, Router};
 
 fn main() {
     let port = "127.0.0.1:4221";
     let mut r = Router::new(port);
+    r.set_access_log(print_access_log);
 
-    r.handle_func("/", base_handler, vec!["GET"]);
     r.handle_func("/echo/:?", echo_handler, vec!["GET"]);
-    r.handle_func("/user-agent", user_agent_handler, vec!["GET"]);
-    r.handle_func("/files/:?", files_handler, vec!["GET", "POST"]);
+    r.handle_result("/user-agent", user_agent_handler, vec!["GET"]);
+    r.handle_func("/files/:?", files_handler, vec!["GET", "POST", "DELETE"]);
     r.handle_func("/json", json_handler, vec!["GET"]);
+    // last, so its wildcard doesn't take paths meant for the routes above
+    r.serve_dir("/", "public");
+    for warning in r.unreachable_routes() {
+        eprintln!("warning: {}", warning);
+    }
 
     println!("Listening on port {}", port);
     if let Err(e) = r.serve() {
@@ -25,20 +30,19 @@ fn json_handler(_req: &Request) -> Response {
     Response::json(200, data)
 }
 
-fn base_handler(_req: &Request) -> Response {
-    Response::file(200, "index.html")
-}
-
 fn echo_handler(req: &Request) -> Response {
     let x = req.path.strip_prefix("/echo/").unwrap().to_owned();
 
     Response::new(200, x)
 }
 
-fn user_agent_handler(req: &Request) -> Response {
-    let agent = req.headers.get("User-Agent").unwrap().to_owned();
+fn user_agent_handler(req: &Request) -> Result<Response, Box<dyn Error>> {
+    let agent = req
+        .header("User-Agent")
+        .ok_or("missing User-Agent header")?
+        .to_owned();
 
-    Response::new(200, agent)
+    Ok(Response::new(200, agent))
 }
 
 fn files_handler(req: &Request) -> Response {
@@ -46,19 +50,23 @@ fn files_handler(req: &Request) -> Response {
     let args: Vec<String> = env::args().collect();
     let directory = env::current_dir()
         .unwrap()
-        .join(&args.get(2).expect("missing directory param"));
-    let file_path = directory.join(filename);
-    let contents = fs::read_to_string(file_path.clone());
-
-    if req.method == "POST" {
-        fs::write(file_path, req.body.clone()).expect("unable to write");
-        return Response::empty(201);
-    }
+        .join(args.get(2).expect("missing directory param"));
+    let Some(file_path) = resolve_path(directory, filename) else {
+        return Response::new(403, "forbidden");
+    };
 
-    if let Err(e) = contents {
-        return Response::new(404, e);
+    match req.method {
+        Method::Post => {
+            fs::write(file_path, &req.body).expect("unable to write");
+            Response::empty(201)
+        }
+        Method::Delete => match fs::remove_file(file_path) {
+            Ok(()) => Response::empty(204),
+            Err(e) => Response::new(404, e),
+        },
+        _ => match fs::read(file_path) {
+            Ok(contents) => Response::bytes(200, contents),
+            Err(e) => Response::new(404, e),
+        },
     }
-
-    let contents = contents.unwrap();
-    Response::new(200, contents).add_header("Content-Type", "application/octet-stream")
 }
@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+/// An HTTP request method
+///
+/// Parsed ignoring case, so `"get"` and `"GET"` are both `Method::Get`. Methods without a
+/// variant are kept upper cased in `Other`
+///
+/// # Example
+///
+/// ```
+/// use http_library::Method;
+///
+/// assert_eq!(Method::from("get"), Method::Get);
+/// assert_eq!(Method::from("purge"), Method::Other("PURGE".to_owned()));
+/// assert_eq!(Method::Post.to_string(), "POST");
+/// assert!(Method::Head == "HEAD");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+    Head,
+    Options,
+    Other(String),
+}
+
+impl Method {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Patch => "PATCH",
+            Method::Head => "HEAD",
+            Method::Options => "OPTIONS",
+            Method::Other(m) => m,
+        }
+    }
+}
+
+impl From<&str> for Method {
+    fn from(s: &str) -> Method {
+        match s.to_ascii_uppercase().as_str() {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "PATCH" => Method::Patch,
+            "HEAD" => Method::Head,
+            "OPTIONS" => Method::Options,
+            other => Method::Other(other.to_owned()),
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for Method {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for Method {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ignoring_case() {
+        assert_eq!(Method::from("GET"), Method::Get);
+        assert_eq!(Method::from("get"), Method::Get);
+        assert_eq!(Method::from("Options"), Method::Options);
+        assert_eq!(Method::from("Purge"), Method::Other("PURGE".to_owned()));
+        assert_eq!(Method::from("purge"), Method::from("PURGE"));
+    }
+
+    #[test]
+    fn compares_with_strings() {
+        assert!(Method::Delete == "DELETE");
+        assert!(Method::Delete == "delete");
+        assert!(Method::Get != "POST");
+    }
+}
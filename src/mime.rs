@@ -0,0 +1,119 @@
+//! Common content types and helpers for picking one
+//!
+//! # Example
+//!
+//! ```
+//! use http_library::{mime, Request, Response};
+//!
+//! fn download(_req: &Request) -> Response {
+//!     Response::bytes(200, vec![0x1f, 0x8b, 0x08]).content_type(mime::OCTET_STREAM)
+//! }
+//! ```
+
+use std::path::Path;
+
+pub const JSON: &str = "application/json";
+pub const HTML: &str = "text/html; charset=utf-8";
+pub const PLAIN: &str = "text/plain; charset=utf-8";
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Guesses the content type of a file from its extension
+///
+/// Unknown or missing extensions are `application/octet-stream`
+pub fn from_path(path: impl AsRef<Path>) -> &'static str {
+    let ext = match path.as_ref().extension().and_then(|e| e.to_str()) {
+        Some(ext) => ext.to_ascii_lowercase(),
+        None => return OCTET_STREAM,
+    };
+
+    match ext.as_str() {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => JSON,
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "xml" => "application/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "wasm" => "application/wasm",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        _ => OCTET_STREAM,
+    }
+}
+
+/// How much an `Accept` header value wants `mime`, from 0 (not at all) to 1
+///
+/// The most specific matching range decides, so `text/html` beats `text/*` which beats `*/*`.
+/// Parameters other than `q` are ignored
+pub(crate) fn quality(accept: &str, mime: &str) -> f32 {
+    let (ty, subtype) = mime.split_once('/').unwrap_or((mime, ""));
+
+    let mut best: Option<(u8, f32)> = None;
+    for range in accept.split(',') {
+        let mut parts = range.split(';').map(str::trim);
+        let (range_ty, range_subtype) = match parts.next().and_then(|r| r.split_once('/')) {
+            Some(r) => r,
+            None => continue,
+        };
+
+        let specificity = if range_ty == "*" && range_subtype == "*" {
+            0
+        } else if range_ty.eq_ignore_ascii_case(ty) && range_subtype == "*" {
+            1
+        } else if range_ty.eq_ignore_ascii_case(ty) && range_subtype.eq_ignore_ascii_case(subtype) {
+            2
+        } else {
+            continue;
+        };
+        let q = parts
+            .find_map(|p| p.strip_prefix("q="))
+            .and_then(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0);
+
+        if best.is_none_or(|(s, _)| specificity > s) {
+            best = Some((specificity, q));
+        }
+    }
+
+    best.map_or(0.0, |(_, q)| q)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighs_accept_ranges() {
+        let accept = "application/json;q=0.9, text/html, text/*;q=0.5, */*;q=0.1";
+        assert_eq!(quality(accept, "text/html"), 1.0);
+        assert_eq!(quality(accept, "application/json"), 0.9);
+        assert_eq!(quality(accept, "text/plain"), 0.5);
+        assert_eq!(quality(accept, "image/png"), 0.1);
+        assert_eq!(quality("text/html;q=0", "text/html"), 0.0);
+        assert_eq!(quality("text/html", "application/json"), 0.0);
+        assert_eq!(quality("TEXT/HTML", "text/html"), 1.0);
+    }
+
+    #[test]
+    fn detects_by_extension() {
+        assert_eq!(from_path("index.html"), "text/html");
+        assert_eq!(from_path("static/app.css"), "text/css");
+        assert_eq!(from_path("app.min.js"), "text/javascript");
+        assert_eq!(from_path("data.json"), "application/json");
+        assert_eq!(from_path("LOGO.PNG"), "image/png");
+        assert_eq!(from_path("font.woff2"), "font/woff2");
+        assert_eq!(from_path("archive.tar.xz"), "application/octet-stream");
+        assert_eq!(from_path("Makefile"), "application/octet-stream");
+    }
+}
@@ -0,0 +1,166 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// One field of a `multipart/form-data` body, see `Request::multipart`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    /// Form field name from `Content-Disposition`
+    pub name: String,
+    /// Name of the uploaded file, `None` for plain fields
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+/// Error from parsing an invalid multipart body
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartError {
+    msg: &'static str,
+}
+
+impl MultipartError {
+    fn new(msg: &'static str) -> MultipartError {
+        MultipartError { msg }
+    }
+}
+
+impl Display for MultipartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid multipart body: {}", self.msg)
+    }
+}
+
+impl Error for MultipartError {}
+
+/// Returns the `boundary` parameter of a `multipart/form-data` content type
+pub fn boundary(content_type: &str) -> Result<&str, MultipartError> {
+    let mut params = content_type.split(';').map(str::trim);
+    let mime = params.next().unwrap_or("");
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return Err(MultipartError::new(
+            "content type is not multipart/form-data",
+        ));
+    }
+
+    params
+        .find_map(|p| {
+            let (k, v) = p.split_once('=')?;
+            k.trim().eq_ignore_ascii_case("boundary").then(|| {
+                let v = v.trim();
+                v.strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(v)
+            })
+        })
+        .filter(|b| !b.is_empty())
+        .ok_or(MultipartError::new("missing boundary"))
+}
+
+/// Splits `body` into parts separated by `boundary`
+///
+/// Anything before the first boundary or after the closing one is ignored
+pub fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>, MultipartError> {
+    let delimiter = format!("--{}", boundary);
+    let delimiter = delimiter.as_bytes();
+    let separator = [b"\r\n", delimiter].concat();
+
+    let mut pos =
+        find(body, delimiter, 0).ok_or(MultipartError::new("missing boundary"))? + delimiter.len();
+    let mut parts = vec![];
+    loop {
+        let rest = &body[pos..];
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        if !rest.starts_with(b"\r\n") {
+            return Err(MultipartError::new("boundary not followed by a line break"));
+        }
+        pos += 2;
+
+        let head_end =
+            find(body, b"\r\n\r\n", pos).ok_or(MultipartError::new("unterminated part headers"))?;
+        let head = std::str::from_utf8(&body[pos..head_end])
+            .map_err(|_| MultipartError::new("part headers are not utf-8"))?;
+        let data_start = head_end + 4;
+        let data_end = find(body, &separator, data_start)
+            .ok_or(MultipartError::new("missing closing boundary"))?;
+
+        let mut part = part_from_headers(head)?;
+        part.data = body[data_start..data_end].to_vec();
+        parts.push(part);
+
+        pos = data_end + separator.len();
+    }
+}
+
+fn part_from_headers(head: &str) -> Result<Part, MultipartError> {
+    let mut name = None;
+    let mut filename = None;
+    let mut content_type = None;
+
+    for line in head.split("\r\n") {
+        let Some((key, val)) = line.split_once(':') else {
+            continue;
+        };
+        let val = val.trim();
+
+        if key.trim().eq_ignore_ascii_case("Content-Type") {
+            content_type = Some(val.to_owned());
+        } else if key.trim().eq_ignore_ascii_case("Content-Disposition") {
+            for param in val.split(';').skip(1) {
+                let Some((k, v)) = param.split_once('=') else {
+                    continue;
+                };
+                let v = v.trim();
+                let v = v
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(v)
+                    .to_owned();
+
+                match k.trim().to_ascii_lowercase().as_str() {
+                    "name" => name = Some(v),
+                    "filename" => filename = Some(v),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(Part {
+        name: name.ok_or(MultipartError::new("part without a name"))?,
+        filename,
+        content_type,
+        data: vec![],
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_boundary() {
+        assert_eq!(boundary("multipart/form-data; boundary=abc"), Ok("abc"));
+        assert_eq!(boundary("Multipart/Form-Data; boundary=\"a b\""), Ok("a b"));
+        assert!(boundary("multipart/form-data").is_err());
+        assert!(boundary("text/plain; boundary=abc").is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_body() {
+        let body = b"--xyz\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nno end";
+        assert_eq!(
+            parse(body, "xyz"),
+            Err(MultipartError::new("missing closing boundary"))
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::Response;
+
+/// Caps how many requests each client IP can make per window, set with `Router::set_rate_limit`
+///
+/// Windows are fixed: a client's first request starts one and its count resets once `window`
+/// has passed. Requests over the limit get a 429 with `Retry-After`. Counts are shared by every
+/// worker thread
+///
+/// # Example
+///
+/// ```
+/// use http_library::{RateLimit, Router};
+/// use std::time::Duration;
+///
+/// let mut r = Router::new("127.0.0.1:12345");
+/// r.set_rate_limit(RateLimit::new(100, Duration::from_secs(60)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    limit: u32,
+    window: Duration,
+    clients: Arc<Mutex<HashMap<IpAddr, (Instant, u32)>>>,
+}
+
+/// Clients tracked before windows that have ended are cleared out
+const PRUNE_THRESHOLD: usize = 10_000;
+
+impl RateLimit {
+    /// Allows `limit` requests per client every `window`
+    pub fn new(limit: u32, window: Duration) -> RateLimit {
+        RateLimit {
+            limit,
+            window,
+            clients: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Counts a request from `ip`, returning the response to send instead if it is over the limit
+    pub(crate) fn check(&self, ip: IpAddr) -> Option<Response> {
+        let now = Instant::now();
+        let mut clients = self.clients.lock().expect("failed to acquire lock");
+
+        if clients.len() >= PRUNE_THRESHOLD {
+            clients.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+        }
+
+        let (start, count) = clients.entry(ip).or_insert((now, 0));
+        if now.duration_since(*start) >= self.window {
+            *start = now;
+            *count = 0;
+        }
+        if *count < self.limit {
+            *count += 1;
+            return None;
+        }
+
+        let remaining = self.window.saturating_sub(now.duration_since(*start));
+        // round up so clients don't come back a moment too early
+        let retry_after = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        Some(
+            Response::new(429, "too many requests")
+                .with_header("Retry-After", &retry_after.to_string()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resets_after_window() {
+        let limit = RateLimit::new(1, Duration::from_millis(50));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+
+        assert!(limit.check(ip).is_none());
+        let res = limit.check(ip).unwrap();
+        assert_eq!(res.code(), 429);
+        assert_eq!(res.headers().get("Retry-After"), Some("1"));
+        assert!(limit.check(other).is_none());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limit.check(ip).is_none());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::Response;
+
+/// Hardening headers added to every response, set with `Router::set_security_headers`
+///
+/// Sends `X-Content-Type-Options: nosniff` and `X-Frame-Options: DENY`, plus
+/// `Strict-Transport-Security` once `hsts` is called. Headers a handler or middleware already
+/// set are left alone, so single routes can differ
+///
+/// # Example
+///
+/// ```
+/// use http_library::{Router, SecurityHeaders};
+///
+/// let mut r = Router::new("127.0.0.1:12345");
+/// r.set_security_headers(SecurityHeaders::new().hsts(31536000, true));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityHeaders {
+    hsts: Option<String>,
+}
+
+impl SecurityHeaders {
+    /// `nosniff` and `DENY`, without `Strict-Transport-Security`
+    pub fn new() -> SecurityHeaders {
+        SecurityHeaders { hsts: None }
+    }
+
+    /// Tells browsers to only use HTTPS for the next `max_age` seconds, for subdomains too if
+    /// `include_subdomains` is set
+    ///
+    /// Only worth sending when the server is reached over HTTPS, browsers ignore it otherwise
+    pub fn hsts(mut self, max_age: u64, include_subdomains: bool) -> SecurityHeaders {
+        let mut value = format!("max-age={}", max_age);
+        if include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        self.hsts = Some(value);
+        self
+    }
+
+    /// Adds the headers `res` doesn't already have
+    pub(crate) fn apply(&self, res: &mut Response) {
+        let mut set_default = |name: &str, value: &str| {
+            if !res.headers.contains(name) {
+                res.set_header(name, value);
+            }
+        };
+
+        set_default("X-Content-Type-Options", "nosniff");
+        set_default("X-Frame-Options", "DENY");
+        if let Some(ref hsts) = self.hsts {
+            set_default("Strict-Transport-Security", hsts);
+        }
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> SecurityHeaders {
+        SecurityHeaders::new()
+    }
+}
@@ -1,11 +1,32 @@
 use std::{
+    error::Error,
+    fmt::Display,
+    io,
+    panic::{self, AssertUnwindSafe},
     sync::{mpsc, Arc, Mutex},
     thread,
+    time::Duration,
 };
 
+/// How often `ThreadPool::execute_until` checks whether to stop waiting for room in the queue
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Fixed set of worker threads running jobs from a bounded queue, the one `Router::serve` hands
+/// connections to
+///
+/// # Example
+///
+/// ```
+/// use http_library::ThreadPool;
+///
+/// let pool = ThreadPool::build(2, 16).unwrap();
+/// let job = pool.execute(|| println!("hi from a worker")).unwrap();
+/// assert!(job.join().is_ok());
+/// ```
 pub struct ThreadPool {
-    workers: Vec<Worker>,
-    sender: Option<mpsc::Sender<Job>>,
+    workers: Mutex<Vec<Worker>>,
+    sender: Option<mpsc::SyncSender<Job>>,
+    receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
 }
 
 type Job = Box<dyn FnOnce() + Send + 'static>;
@@ -13,38 +34,99 @@ type Job = Box<dyn FnOnce() + Send + 'static>;
 impl ThreadPool {
     /// Create a new ThreadPool.
     ///
-    /// The size is the number of threads in the pool
-    ///
-    /// # Panics
+    /// The size is the number of threads in the pool and `queue_size` how many jobs can wait
+    /// for a free thread
     ///
-    /// The 'new' function will panic if the size is zero
-    pub fn build(size: usize) -> Result<ThreadPool, PoolCreationError> {
+    /// Fails if the size is zero or a worker thread can't be spawned
+    pub fn build(size: usize, queue_size: usize) -> Result<ThreadPool, PoolCreationError> {
         if size == 0 {
             return Err(PoolCreationError::ZeroThreadsError);
         }
 
-        let (sender, receiver) = mpsc::channel();
+        let (sender, receiver) = mpsc::sync_channel(queue_size);
         let receiver = Arc::new(Mutex::new(receiver));
         let mut workers = Vec::with_capacity(size);
 
         for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+            let worker =
+                Worker::new(id, Arc::clone(&receiver)).map_err(PoolCreationError::SpawnError)?;
+            workers.push(worker);
         }
 
         Ok(ThreadPool {
-            workers,
+            workers: Mutex::new(workers),
             sender: Some(sender),
+            receiver,
         })
     }
 
-    // Sends job to thread pool to execute
-    pub fn execute<F>(&self, f: F)
+    /// Sends job to thread pool to execute, waiting for room if the queue is full
+    ///
+    /// The returned handle tells when the job is done. Dropping it is fine, a panic in the job
+    /// is then left to the worker as if there were no handle
+    pub fn execute<F>(&self, f: F) -> Result<JobHandle, PoolClosedError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.replace_dead_workers();
+        let (job, handle) = with_handle(f);
+
+        let sender = self.sender.as_ref().ok_or(PoolClosedError)?;
+        sender.send(job).map_err(|_| PoolClosedError)?;
+        Ok(handle)
+    }
+
+    /// Same as `execute`, except that while the queue is full `cancelled` is checked regularly
+    /// and the job dropped without running once it returns true, giving `Ok(None)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_library::ThreadPool;
+    ///
+    /// let pool = ThreadPool::build(1, 16).unwrap();
+    /// let job = pool.execute_until(|| println!("hi from a worker"), || false).unwrap();
+    /// assert!(job.unwrap().join().is_ok());
+    /// ```
+    pub fn execute_until<F>(
+        &self,
+        f: F,
+        cancelled: impl Fn() -> bool,
+    ) -> Result<Option<JobHandle>, PoolClosedError>
     where
         F: FnOnce() + Send + 'static,
     {
-        let job = Box::new(f);
+        self.replace_dead_workers();
+        let (mut job, handle) = with_handle(f);
+
+        let sender = self.sender.as_ref().ok_or(PoolClosedError)?;
+        loop {
+            match sender.try_send(job) {
+                Ok(()) => return Ok(Some(handle)),
+                Err(mpsc::TrySendError::Full(j)) => job = j,
+                Err(mpsc::TrySendError::Disconnected(_)) => return Err(PoolClosedError),
+            }
+            if cancelled() {
+                return Ok(None);
+            }
+            thread::sleep(QUEUE_POLL_INTERVAL);
+        }
+    }
 
-        self.sender.as_ref().unwrap().send(job).unwrap();
+    /// Replaces workers whose thread has exited so the pool doesn't lose capacity
+    ///
+    /// Workers only exit on their own if something escapes the panic handling around a job
+    fn replace_dead_workers(&self) {
+        let mut workers = self.workers.lock().expect("failed to acquire lock");
+
+        for worker in workers.iter_mut() {
+            if worker.thread.as_ref().is_some_and(|t| t.is_finished()) {
+                // if the thread can't be spawned now the worker stays dead until the next job
+                if let Ok(new) = Worker::new(worker.id, Arc::clone(&self.receiver)) {
+                    *worker = new;
+                }
+            }
+        }
     }
 }
 
@@ -52,24 +134,55 @@ impl Drop for ThreadPool {
     fn drop(&mut self) {
         drop(self.sender.take());
 
-        for worker in &mut self.workers {
-            println!("Shutting down worker {}", worker.id);
-
+        let workers = self.workers.get_mut().expect("failed to acquire lock");
+        for worker in workers.iter_mut() {
             if let Some(thread) = worker.thread.take() {
-                thread.join().unwrap();
+                // a worker that died has already been reported by its panic
+                let _ = thread.join();
             }
         }
     }
 }
 
+/// Wraps `f` into a job that reports how it went to the returned handle
+fn with_handle<F>(f: F) -> (Job, JobHandle)
+where
+    F: FnOnce() + Send + 'static,
+{
+    let (done, result) = mpsc::sync_channel(1);
+    let job = Box::new(move || {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(f));
+        if let Err(mpsc::TrySendError::Disconnected(Err(payload))) = done.try_send(outcome) {
+            panic::resume_unwind(payload);
+        }
+    });
+
+    (job, JobHandle { result })
+}
+
+/// Tells when a job sent with `ThreadPool::execute` has finished
+pub struct JobHandle {
+    result: mpsc::Receiver<thread::Result<()>>,
+}
+
+impl JobHandle {
+    /// Waits for the job to finish, returning the panic payload if it panicked
+    pub fn join(self) -> thread::Result<()> {
+        self.result
+            .recv()
+            .unwrap_or_else(|_| Err(Box::new("job was dropped without running")))
+    }
+}
+
 struct Worker {
     id: usize,
     thread: Option<thread::JoinHandle<()>>,
 }
 
 impl Worker {
-    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
-        let thread = thread::spawn(move || loop {
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> io::Result<Worker> {
+        let builder = thread::Builder::new().name(format!("http-worker-{}", id));
+        let thread = builder.spawn(move || loop {
             // 'let' drops any temporary values used in the expression on the right hand side of the
             // equals sign when it ends.
             // However 'while let', 'if let' and 'match' do not
@@ -80,25 +193,217 @@ impl Worker {
 
             match message {
                 Ok(job) => {
-                    // println!("Worker {id} got a job; executing.");
-
-                    job();
-                }
-                Err(_) => {
-                    println!("Worker {id} disconnected; shutting down.");
-                    break;
+                    // keep the worker alive if the job panics
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
                 }
+                // the pool was dropped
+                Err(_) => break,
             };
-        });
+        })?;
 
-        Worker {
+        Ok(Worker {
             id,
             thread: Some(thread),
-        }
+        })
     }
 }
 
+/// Why the pool of worker threads handling connections couldn't be started
 #[derive(Debug)]
 pub enum PoolCreationError {
+    /// The pool was asked for zero threads
     ZeroThreadsError,
+    /// The OS refused to spawn a worker thread
+    SpawnError(io::Error),
+}
+
+impl Display for PoolCreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PoolCreationError::ZeroThreadsError => write!(f, "thread pool size must be non-zero"),
+            PoolCreationError::SpawnError(e) => write!(f, "could not spawn worker thread: {}", e),
+        }
+    }
+}
+
+/// Error from sending a job to a pool that is shutting down
+#[derive(Debug)]
+pub struct PoolClosedError;
+
+impl Display for PoolClosedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "thread pool is shut down")
+    }
+}
+
+impl Error for PoolClosedError {}
+
+impl Error for PoolCreationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PoolCreationError::ZeroThreadsError => None,
+            PoolCreationError::SpawnError(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Panic payload that panics again when dropped, which escapes `catch_unwind` in the worker
+    struct Bomb;
+
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            panic!("bomb dropped");
+        }
+    }
+
+    #[test]
+    fn shutdown_prints_nothing() {
+        // stdout can't be captured in process, so the pool is run by a copy of this test in a
+        // child process
+        if std::env::var_os("THREAD_POOL_SHUTDOWN_CHILD").is_some() {
+            let pool = ThreadPool::build(2, 2).unwrap();
+            pool.execute(|| {}).unwrap();
+            drop(pool);
+            return;
+        }
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "threads::tests::shutdown_prints_nothing",
+                "--exact",
+                "--nocapture",
+            ])
+            .env("THREAD_POOL_SHUTDOWN_CHILD", "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+
+        assert!(output.status.success());
+        assert!(stdout.contains("1 passed"));
+        assert!(!stdout.to_lowercase().contains("worker"));
+    }
+
+    #[test]
+    fn replaces_dead_workers() {
+        let pool = ThreadPool::build(1, 1).unwrap();
+        // sent as is, since `execute` would hand the panic to the job's handle
+        let job: Job = Box::new(|| panic::panic_any(Bomb));
+        pool.sender.as_ref().unwrap().send(job).unwrap();
+
+        let dead = || {
+            let workers = pool.workers.lock().unwrap();
+            workers[0].thread.as_ref().unwrap().is_finished()
+        };
+        while !dead() {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        pool.execute(move || tx.send(42).unwrap()).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(42));
+    }
+
+    #[test]
+    fn names_worker_threads() {
+        let pool = ThreadPool::build(1, 1).unwrap();
+        let (tx, rx) = mpsc::channel();
+        pool.execute(move || {
+            tx.send(thread::current().name().map(str::to_owned))
+                .unwrap()
+        })
+        .unwrap();
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)),
+            Ok(Some("http-worker-0".to_owned()))
+        );
+    }
+
+    #[test]
+    fn handle_reports_job_outcome() {
+        let mut pool = ThreadPool::build(1, 1).unwrap();
+
+        assert!(pool.execute(|| {}).unwrap().join().is_ok());
+        let payload = pool.execute(|| panic!("oops")).unwrap().join().unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"oops"));
+
+        // the worker survives a panic it wasn't left to handle
+        let (tx, rx) = mpsc::channel();
+        pool.execute(move || tx.send(42).unwrap()).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(42));
+
+        pool.sender.take();
+        assert!(matches!(pool.execute(|| {}), Err(PoolClosedError)));
+    }
+
+    #[test]
+    fn execute_waits_while_queue_is_full() {
+        let pool = Arc::new(ThreadPool::build(1, 1).unwrap());
+        let (started_tx, started) = mpsc::channel();
+        let (release, release_rx) = mpsc::channel::<()>();
+        pool.execute(move || {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        })
+        .unwrap();
+        started.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        // the only worker is busy, so one job fits in the queue and the next has to wait
+        let (tx, rx) = mpsc::channel();
+        let queued = tx.clone();
+        pool.execute(move || queued.send(1).unwrap()).unwrap();
+        let (sent_tx, sent) = mpsc::channel();
+        let sender = thread::spawn({
+            let pool = Arc::clone(&pool);
+            move || {
+                pool.execute(move || tx.send(2).unwrap()).unwrap();
+                sent_tx.send(()).unwrap();
+            }
+        });
+        assert!(sent.recv_timeout(Duration::from_millis(100)).is_err());
+
+        release.send(()).unwrap();
+        sent.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(1));
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(2));
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn execute_until_gives_up_while_queue_is_full() {
+        let pool = ThreadPool::build(1, 1).unwrap();
+        let (started_tx, started) = mpsc::channel();
+        let (release, release_rx) = mpsc::channel::<()>();
+        pool.execute(move || {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        })
+        .unwrap();
+        started.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let queued = tx.clone();
+        let job = pool.execute_until(move || queued.send(1).unwrap(), || true);
+        assert!(job.unwrap().is_some());
+
+        // the queue is full, so the job is dropped as soon as it is cancelled
+        let checks = std::cell::Cell::new(0);
+        let job = pool.execute_until(
+            move || tx.send(2).unwrap(),
+            || {
+                checks.set(checks.get() + 1);
+                checks.get() == 3
+            },
+        );
+        assert!(job.unwrap().is_none());
+        assert_eq!(checks.get(), 3);
+
+        release.send(()).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(1));
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_err());
+    }
 }
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+/// Decodes `%XX` escapes in `input`
+///
+/// Returns `None` if an escape is malformed or the decoded bytes are not valid utf-8
+pub fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3)?;
+            let hex = std::str::from_utf8(hex).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(out).ok()
+}
+
+/// Parses a query string like `a=1&b=two` into a map
+///
+/// `+` is treated as a space and keys and values are percent-decoded, falling back to the raw
+/// text if decoding fails. When a key is repeated the last value wins.
+pub fn parse_query(query: &str) -> HashMap<String, String> {
+    let decode = |s: &str| {
+        let s = s.replace('+', " ");
+        percent_decode(&s).unwrap_or(s)
+    };
+
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((k, v)) => (decode(k), decode(v)),
+            None => (decode(pair), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(percent_decode("a%20b").as_deref(), Some("a b"));
+        assert_eq!(percent_decode("%ZZ"), None);
+        assert_eq!(percent_decode("%2"), None);
+    }
+
+    #[test]
+    fn parses_query() {
+        let q = parse_query("foo=bar&x=1&x=2&name=hello%20world&flag");
+
+        assert_eq!(q.get("foo").unwrap(), "bar");
+        assert_eq!(q.get("x").unwrap(), "2");
+        assert_eq!(q.get("name").unwrap(), "hello world");
+        assert_eq!(q.get("flag").unwrap(), "");
+        assert!(parse_query("").is_empty());
+    }
+}